@@ -0,0 +1,16 @@
+/// Selects how a [`crate::Window`] carries out its actions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Backend {
+    /// Shell out to the `wmctrl` binary. The default, and the only backend
+    /// available without the `ewmh` feature.
+    #[default]
+    Wmctrl,
+    /// Carry out window actions by talking to the X server directly via EWMH
+    /// `ClientMessage`s through `x11rb` instead of running the `wmctrl` binary.
+    /// Requires the `ewmh` feature. [`crate::Window::activate`] still runs `wmctrl -d`
+    /// to look up the current desktop, [`crate::Window::refresh`] and
+    /// [`crate::get_windows`] still run `wmctrl -l`, and
+    /// [`crate::Window::frame_extents`] runs `xprop`.
+    #[cfg(feature = "ewmh")]
+    Ewmh,
+}
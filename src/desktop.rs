@@ -0,0 +1,22 @@
+use crate::error::WmctrlError;
+use crate::utils::wmctrl;
+
+/// Get the number of the desktop currently shown to the user.
+///
+/// This parses the `*` marker out of `wmctrl -d`.
+pub fn get_current_desktop() -> Result<String, WmctrlError> {
+    let output = wmctrl(&["-d".to_string()])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    for line in stdout.lines() {
+        let mut fields = line.split_whitespace();
+        let number = fields.next();
+        let marker = fields.next();
+
+        if let (Some(number), Some("*")) = (number, marker) {
+            return Ok(number.to_string());
+        }
+    }
+
+    Err(WmctrlError::NoCurrentDesktop)
+}
@@ -0,0 +1,53 @@
+use std::fmt;
+
+/// Errors that can occur while shelling out to `wmctrl` or acting on a [`crate::Window`].
+#[derive(Debug)]
+pub enum WmctrlError {
+    /// The `wmctrl` (or `xrandr`/`xprop`) binary could not be found on `$PATH`.
+    BinaryNotFound,
+    /// The command ran but exited with a non-zero status.
+    NonZeroExit { code: Option<i32>, stderr: String },
+    /// The window this action targeted no longer exists.
+    WindowNotFound,
+    /// No desktop is currently marked as active by the window manager.
+    NoCurrentDesktop,
+    /// The `ewmh` backend failed to talk to the X server (connection, atom lookup, or
+    /// property/message request failure).
+    ConnectionFailed(String),
+    /// Spawning or waiting on the child process failed for a reason other than the
+    /// binary being missing (e.g. permission denied, or the syscall was interrupted).
+    Io(std::io::Error),
+}
+
+impl fmt::Display for WmctrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WmctrlError::BinaryNotFound => {
+                write!(f, "a required binary ('wmctrl', 'xrandr' or 'xprop') could not be found")
+            }
+            WmctrlError::NonZeroExit { code, stderr } => write!(
+                f,
+                "command exited with status {}: {}",
+                code.map_or_else(|| "unknown".to_string(), |c| c.to_string()),
+                stderr.trim()
+            ),
+            WmctrlError::WindowNotFound => write!(f, "the target window no longer exists"),
+            WmctrlError::NoCurrentDesktop => {
+                write!(f, "no desktop is currently marked as active")
+            }
+            WmctrlError::ConnectionFailed(reason) => {
+                write!(f, "failed to talk to the X server: {}", reason)
+            }
+            WmctrlError::Io(e) => write!(f, "failed to run command: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for WmctrlError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WmctrlError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,227 @@
+//! Native EWMH backend.
+//!
+//! Implements the same window operations as the `wmctrl`-shelling backend, but by
+//! sending `_NET_*` `ClientMessage` events (and property changes) directly to the
+//! X server via `x11rb`. Only compiled in with the `ewmh` feature.
+
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{ClientMessageEvent, ConnectionExt, EventMask, PropMode};
+use x11rb::rust_connection::RustConnection;
+use x11rb::wrapper::ConnectionExt as _;
+
+use crate::error::WmctrlError;
+use crate::state::{Action, Property};
+use crate::transformation::Transformation;
+
+const SOURCE_INDICATION_APPLICATION: u32 = 1;
+
+fn connect() -> Result<(RustConnection, usize), WmctrlError> {
+    x11rb::connect(None).map_err(|e| WmctrlError::ConnectionFailed(e.to_string()))
+}
+
+fn parse_window_id(id: &str) -> Result<u32, WmctrlError> {
+    u32::from_str_radix(id.trim_start_matches("0x"), 16).map_err(|_| WmctrlError::WindowNotFound)
+}
+
+fn intern(conn: &RustConnection, name: &[u8]) -> Result<u32, WmctrlError> {
+    conn.intern_atom(false, name)
+        .map_err(|e| WmctrlError::ConnectionFailed(e.to_string()))?
+        .reply()
+        .map(|reply| reply.atom)
+        .map_err(|e| WmctrlError::ConnectionFailed(e.to_string()))
+}
+
+/// Map an [`Action`] to the `_NET_WM_STATE` `data[0]` code the EWMH spec assigns it.
+fn action_code(action: Action) -> u32 {
+    match action {
+        Action::Remove => 0,
+        Action::Add => 1,
+        Action::Toggle => 2,
+    }
+}
+
+/// The `_NET_WM_STATE_*` atom name(s) a [`Property`] maps to.
+///
+/// `Property::Maximized` covers both axes, matching the `Wmctrl` backend's
+/// "maximized_vert,maximized_horz" state string, so it maps to two atoms packed into a
+/// single message's `data[1]`/`data[2]`, per the EWMH spec's two-property `_NET_WM_STATE` form.
+fn state_atom_names(property: Property) -> &'static [&'static [u8]] {
+    match property {
+        Property::Maximized => &[b"_NET_WM_STATE_MAXIMIZED_VERT", b"_NET_WM_STATE_MAXIMIZED_HORZ"],
+        Property::Minimized | Property::Hidden => &[b"_NET_WM_STATE_HIDDEN"],
+        Property::Fullscreen => &[b"_NET_WM_STATE_FULLSCREEN"],
+        Property::Shaded => &[b"_NET_WM_STATE_SHADED"],
+        Property::Above => &[b"_NET_WM_STATE_ABOVE"],
+        Property::Below => &[b"_NET_WM_STATE_BELOW"],
+    }
+}
+
+/// The `_NET_MOVERESIZE_WINDOW` `data[0]` flags for `transformation`: the gravity it
+/// carries in the low byte, OR'd with the source-indication bits and with the
+/// "x/y/width/height present" bit of every field that is not `-1`, so a `-1` field is left
+/// untouched just as with `wmctrl -e`.
+fn moveresize_flags(transformation: &Transformation) -> u32 {
+    const X: u32 = 1 << 8;
+    const Y: u32 = 1 << 9;
+    const WIDTH: u32 = 1 << 10;
+    const HEIGHT: u32 = 1 << 11;
+    let mut flags = transformation.gravity_value() as u32 | (SOURCE_INDICATION_APPLICATION << 12);
+    for (value, bit) in [
+        (transformation.x(), X),
+        (transformation.y(), Y),
+        (transformation.width(), WIDTH),
+        (transformation.height(), HEIGHT),
+    ] {
+        if value != -1 {
+            flags |= bit;
+        }
+    }
+    flags
+}
+
+fn send_client_message(
+    conn: &RustConnection,
+    root: u32,
+    window: u32,
+    message_type: u32,
+    data: [u32; 5],
+) -> Result<(), WmctrlError> {
+    let event = ClientMessageEvent::new(32, window, message_type, data);
+    conn.send_event(
+        false,
+        root,
+        EventMask::SUBSTRUCTURE_NOTIFY | EventMask::SUBSTRUCTURE_REDIRECT,
+        event,
+    )
+    .map_err(|e| WmctrlError::ConnectionFailed(e.to_string()))?;
+    conn.flush()
+        .map_err(|e| WmctrlError::ConnectionFailed(e.to_string()))
+}
+
+pub(crate) fn exists(id: &str) -> Result<bool, WmctrlError> {
+    let window = parse_window_id(id)?;
+    let (conn, _) = connect()?;
+
+    let cookie = conn
+        .get_window_attributes(window)
+        .map_err(|e| WmctrlError::ConnectionFailed(e.to_string()))?;
+    Ok(cookie.reply().is_ok())
+}
+
+pub(crate) fn activate(id: &str) -> Result<(), WmctrlError> {
+    let window = parse_window_id(id)?;
+    let (conn, screen) = connect()?;
+    let root = conn.setup().roots[screen].root;
+    let atom = intern(&conn, b"_NET_ACTIVE_WINDOW")?;
+    send_client_message(&conn, root, window, atom, [SOURCE_INDICATION_APPLICATION, 0, 0, 0, 0])
+}
+
+pub(crate) fn close(id: &str) -> Result<(), WmctrlError> {
+    let window = parse_window_id(id)?;
+    let (conn, screen) = connect()?;
+    let root = conn.setup().roots[screen].root;
+    let atom = intern(&conn, b"_NET_CLOSE_WINDOW")?;
+    send_client_message(&conn, root, window, atom, [0, SOURCE_INDICATION_APPLICATION, 0, 0, 0])
+}
+
+pub(crate) fn set_desktop(id: &str, desktop: u32) -> Result<(), WmctrlError> {
+    let window = parse_window_id(id)?;
+    let (conn, screen) = connect()?;
+    let root = conn.setup().roots[screen].root;
+    let atom = intern(&conn, b"_NET_WM_DESKTOP")?;
+    send_client_message(&conn, root, window, atom, [desktop, SOURCE_INDICATION_APPLICATION, 0, 0, 0])
+}
+
+pub(crate) fn change_state(id: &str, action: Action, property: Property) -> Result<(), WmctrlError> {
+    let window = parse_window_id(id)?;
+    let (conn, screen) = connect()?;
+    let root = conn.setup().roots[screen].root;
+
+    let message_atom = intern(&conn, b"_NET_WM_STATE")?;
+    let mut data = [action_code(action), 0, 0, SOURCE_INDICATION_APPLICATION, 0];
+    for (slot, name) in data[1..3].iter_mut().zip(state_atom_names(property)) {
+        *slot = intern(&conn, name)?;
+    }
+
+    send_client_message(&conn, root, window, message_atom, data)
+}
+
+pub(crate) fn transform(id: &str, transformation: &Transformation) -> Result<(), WmctrlError> {
+    let window = parse_window_id(id)?;
+    let (conn, screen) = connect()?;
+    let root = conn.setup().roots[screen].root;
+    let atom = intern(&conn, b"_NET_MOVERESIZE_WINDOW")?;
+    let flags = moveresize_flags(transformation);
+
+    send_client_message(
+        &conn,
+        root,
+        window,
+        atom,
+        [
+            flags,
+            transformation.x() as u32,
+            transformation.y() as u32,
+            transformation.width() as u32,
+            transformation.height() as u32,
+        ],
+    )
+}
+
+pub(crate) fn set_name(id: &str, title: &str) -> Result<(), WmctrlError> {
+    let window = parse_window_id(id)?;
+    let (conn, _) = connect()?;
+
+    let name_atom = intern(&conn, b"_NET_WM_NAME")?;
+    let utf8_string_atom = intern(&conn, b"UTF8_STRING")?;
+
+    conn.change_property8(
+        PropMode::REPLACE,
+        window,
+        name_atom,
+        utf8_string_atom,
+        title.as_bytes(),
+    )
+    .map_err(|e| WmctrlError::ConnectionFailed(e.to_string()))?;
+    conn.flush()
+        .map_err(|e| WmctrlError::ConnectionFailed(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn action_code_matches_ewmh_spec() {
+        assert_eq!(action_code(Action::Remove), 0);
+        assert_eq!(action_code(Action::Add), 1);
+        assert_eq!(action_code(Action::Toggle), 2);
+    }
+
+    #[test]
+    fn maximized_maps_to_both_axis_atoms() {
+        assert_eq!(
+            state_atom_names(Property::Maximized),
+            &[b"_NET_WM_STATE_MAXIMIZED_VERT" as &[u8], b"_NET_WM_STATE_MAXIMIZED_HORZ" as &[u8]]
+        );
+    }
+
+    #[test]
+    fn other_properties_map_to_a_single_atom() {
+        assert_eq!(state_atom_names(Property::Fullscreen), &[b"_NET_WM_STATE_FULLSCREEN" as &[u8]]);
+        assert_eq!(state_atom_names(Property::Above), &[b"_NET_WM_STATE_ABOVE" as &[u8]]);
+    }
+
+    #[test]
+    fn moveresize_flags_carries_gravity_in_the_low_byte() {
+        let transformation = Transformation::new(0, 0, 100, 100).gravity(5);
+        assert_eq!(moveresize_flags(&transformation) & 0xFF, 5);
+    }
+
+    #[test]
+    fn moveresize_flags_sets_xywh_and_source_indication_bits_when_all_fields_given() {
+        let transformation = Transformation::new(0, 0, 100, 100);
+        let flags = moveresize_flags(&transformation);
+        assert_eq!(flags & !0xFF, (0b1111 << 8) | (SOURCE_INDICATION_APPLICATION << 12));
+    }
+}
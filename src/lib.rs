@@ -1,26 +1,78 @@
-use std::process::Command;
+mod backend;
+mod error;
+#[cfg(feature = "ewmh")]
+mod ewmh;
+mod utils;
+pub mod desktop;
+pub mod monitor;
+pub mod query;
+pub mod state;
+pub mod transformation;
+pub mod window;
 
-pub fn list_windows() -> std::process::Output {
-   wmctrl("-l")
+pub use backend::Backend;
+pub use error::WmctrlError;
+pub use monitor::Monitor;
+pub use query::WindowQuery;
+pub use state::{Action, Property, State};
+pub use transformation::Transformation;
+pub use window::Window;
+
+use utils::wmctrl;
+
+/// This equals the -l flag
+pub fn list_windows() -> Result<std::process::Output, WmctrlError> {
+    wmctrl(&["-l".to_string()])
 }
 
-/// This equals the -m flag
-pub fn show_information_about_wm() -> std::process::Output {
-    wmctrl("-m")
+/// List every window managed by the window manager.
+///
+/// This is the equivalent of `wmctrl -l -G -x` and is the only way to obtain [`Window`]s.
+pub fn get_windows() -> Result<Vec<Window>, WmctrlError> {
+    let output = wmctrl(&["-l".to_string(), "-G".to_string(), "-x".to_string()])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let windows = stdout
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+
+            let id = fields.next()?;
+            let desktop = fields.next()?;
+            let _x = fields.next()?;
+            let _y = fields.next()?;
+            let _width = fields.next()?;
+            let _height = fields.next()?;
+            let class = fields.next()?;
+            let client_machine = fields.next()?;
+            let title = fields.collect::<Vec<_>>().join(" ");
+
+            Some(Window::new(
+                id.to_string(),
+                desktop.to_string(),
+                client_machine.to_string(),
+                title,
+                Transformation::new(
+                    _x.parse().ok()?,
+                    _y.parse().ok()?,
+                    _width.parse().ok()?,
+                    _height.parse().ok()?,
+                ),
+                class.to_string(),
+            ))
+        })
+        .collect();
+
+    Ok(windows)
 }
 
-fn wmctrl(args: &str) -> std::process::Output {
-     Command::new("sh")
-        .arg("-c")
-        .arg(format!("wmctrl {}", args))
-        .output()
-        .expect(&format!("failed to execute 'wmctrl {}'", args))
+/// Find the first window whose title contains `title`.
+pub fn find_window_by_title(title: &str) -> Result<Option<Window>, WmctrlError> {
+    let windows = get_windows()?;
+    Ok(windows.into_iter().find(|w| w.title().contains(title)))
 }
 
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn it_works() {
-        assert_eq!(2 + 2, 4);
-    }
+/// This equals the -m flag
+pub fn show_information_about_wm() -> Result<std::process::Output, WmctrlError> {
+    wmctrl(&["-m".to_string()])
 }
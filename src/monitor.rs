@@ -0,0 +1,115 @@
+use crate::error::WmctrlError;
+use crate::utils::run;
+
+/// A physical monitor as reported by `xrandr`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Monitor {
+    name: String,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    primary: bool,
+}
+
+impl Monitor {
+    /// Get the monitor's `xrandr` output name (e.g. `"eDP-1"`).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the monitor's horizontal offset from the root window's origin.
+    pub fn x(&self) -> i32 {
+        self.x
+    }
+
+    /// Get the monitor's vertical offset from the root window's origin.
+    pub fn y(&self) -> i32 {
+        self.y
+    }
+
+    /// Get the monitor's width in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get the monitor's height in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Whether `xrandr` reports this monitor as primary.
+    pub fn primary(&self) -> bool {
+        self.primary
+    }
+}
+
+/// List every connected monitor, in the order `xrandr --query` reports them.
+///
+/// A connected output that `xrandr` reports without a `WxH+X+Y` geometry (one with no
+/// active mode) is left out.
+pub fn get_monitors() -> Result<Vec<Monitor>, WmctrlError> {
+    let output = run("xrandr", &["--query".to_string()])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let monitors = stdout
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let name = fields.next()?;
+            if fields.next()? != "connected" {
+                return None;
+            }
+
+            let mut rest: Vec<&str> = fields.collect();
+            let primary = rest.first() == Some(&"primary");
+            if primary {
+                rest.remove(0);
+            }
+
+            let geometry = rest.first()?;
+            let (width, height, x, y) = parse_geometry(geometry)?;
+
+            Some(Monitor {
+                name: name.to_string(),
+                x,
+                y,
+                width,
+                height,
+                primary,
+            })
+        })
+        .collect();
+
+    Ok(monitors)
+}
+
+/// Parse an xrandr geometry token like `1920x1080+1920+0`.
+fn parse_geometry(geometry: &str) -> Option<(u32, u32, i32, i32)> {
+    let (size, offset) = geometry.split_once('+')?;
+    let (width, height) = size.split_once('x')?;
+    let (x, y) = offset.split_once('+')?;
+
+    Some((
+        width.parse().ok()?,
+        height.parse().ok()?,
+        x.parse().ok()?,
+        y.parse().ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_geometry_token() {
+        assert_eq!(parse_geometry("1920x1080+1920+0"), Some((1920, 1080, 1920, 0)));
+    }
+
+    #[test]
+    fn rejects_malformed_token() {
+        assert_eq!(parse_geometry("1920x1080"), None);
+        assert_eq!(parse_geometry(""), None);
+    }
+}
@@ -0,0 +1,149 @@
+use regex::Regex;
+
+use crate::window::Window;
+
+enum Predicate {
+    ClassContains(String),
+    ClassMatches(Regex),
+    TitleContains(String),
+    TitleMatches(Regex),
+    ClientMachineContains(String),
+    ClientMachineMatches(Regex),
+    OnDesktop(String),
+}
+
+impl Predicate {
+    fn matches(&self, window: &Window) -> bool {
+        match self {
+            Predicate::ClassContains(s) => window.class().contains(s.as_str()),
+            Predicate::ClassMatches(re) => re.is_match(window.class()),
+            Predicate::TitleContains(s) => window.title().contains(s.as_str()),
+            Predicate::TitleMatches(re) => re.is_match(window.title()),
+            Predicate::ClientMachineContains(s) => window.client_machine().contains(s.as_str()),
+            Predicate::ClientMachineMatches(re) => re.is_match(window.client_machine()),
+            Predicate::OnDesktop(desktop) => window.desktop() == desktop,
+        }
+    }
+}
+
+/// A builder that chains predicates to filter a slice of [`Window`]s.
+///
+/// # Examples
+///
+/// ```no_run
+/// use regex::Regex;
+/// use wmctrl::query::WindowQuery;
+///
+/// let windows = wmctrl::get_windows().unwrap();
+/// let terminals = WindowQuery::new()
+///     .class_matches(Regex::new("(?i)term").unwrap())
+///     .on_desktop("1")
+///     .find(&windows);
+/// ```
+#[derive(Default)]
+pub struct WindowQuery {
+    predicates: Vec<Predicate>,
+}
+
+impl WindowQuery {
+    pub fn new() -> WindowQuery {
+        WindowQuery {
+            predicates: Vec::new(),
+        }
+    }
+
+    /// Match windows whose `WM_CLASS` contains `class`.
+    pub fn class_contains(mut self, class: &str) -> WindowQuery {
+        self.predicates.push(Predicate::ClassContains(class.to_string()));
+        self
+    }
+
+    /// Match windows whose `WM_CLASS` matches `regex`.
+    pub fn class_matches(mut self, regex: Regex) -> WindowQuery {
+        self.predicates.push(Predicate::ClassMatches(regex));
+        self
+    }
+
+    /// Match windows whose title contains `title`.
+    pub fn title_contains(mut self, title: &str) -> WindowQuery {
+        self.predicates.push(Predicate::TitleContains(title.to_string()));
+        self
+    }
+
+    /// Match windows whose title matches `regex`.
+    pub fn title_matches(mut self, regex: Regex) -> WindowQuery {
+        self.predicates.push(Predicate::TitleMatches(regex));
+        self
+    }
+
+    /// Match windows whose client machine contains `client_machine`.
+    pub fn client_machine_contains(mut self, client_machine: &str) -> WindowQuery {
+        self.predicates
+            .push(Predicate::ClientMachineContains(client_machine.to_string()));
+        self
+    }
+
+    /// Match windows whose client machine matches `regex`.
+    pub fn client_machine_matches(mut self, regex: Regex) -> WindowQuery {
+        self.predicates.push(Predicate::ClientMachineMatches(regex));
+        self
+    }
+
+    /// Match windows currently on desktop `desktop`.
+    pub fn on_desktop(mut self, desktop: &str) -> WindowQuery {
+        self.predicates.push(Predicate::OnDesktop(desktop.to_string()));
+        self
+    }
+
+    fn is_match(&self, window: &Window) -> bool {
+        self.predicates.iter().all(|p| p.matches(window))
+    }
+
+    /// Run the query against `windows`, returning immutable references to every match.
+    pub fn find<'a>(&self, windows: &'a [Window]) -> Vec<&'a Window> {
+        windows.iter().filter(|w| self.is_match(w)).collect()
+    }
+
+    /// Run the query against `windows`, returning mutable references to every match.
+    pub fn find_mut<'a>(&self, windows: &'a mut [Window]) -> Vec<&'a mut Window> {
+        windows.iter_mut().filter(|w| self.is_match(w)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transformation::Transformation;
+
+    fn window(class: &str, title: &str, desktop: &str) -> Window {
+        Window::new(
+            "0x1".to_string(),
+            desktop.to_string(),
+            "localhost".to_string(),
+            title.to_string(),
+            Transformation::new(0, 0, 100, 100),
+            class.to_string(),
+        )
+    }
+
+    #[test]
+    fn class_contains_matches_substring() {
+        let win = window("firefox.Firefox", "Mozilla Firefox", "0");
+        let query = WindowQuery::new().class_contains("Firefox");
+        assert_eq!(query.find(&[win]).len(), 1);
+    }
+
+    #[test]
+    fn predicates_combine_with_and() {
+        let win = window("xterm.XTerm", "xterm", "1");
+        let query = WindowQuery::new().class_contains("xterm").on_desktop("0");
+        assert!(query.find(&[win]).is_empty());
+    }
+
+    #[test]
+    fn title_matches_regex() {
+        let win = window("code.Code", "main.rs - Visual Studio Code", "0");
+        let query = WindowQuery::new().title_matches(Regex::new("(?i)visual studio").unwrap());
+        assert_eq!(query.find(&[win]).len(), 1);
+    }
+}
@@ -0,0 +1,76 @@
+use std::fmt;
+
+/// The action to apply to a window property via `wmctrl -r <WIN> -b <STARG>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Add,
+    Remove,
+    Toggle,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Action::Add => "add",
+            Action::Remove => "remove",
+            Action::Toggle => "toggle",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// A `_NET_WM_STATE` property understood by the window manager.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Property {
+    Maximized,
+    Minimized,
+    Fullscreen,
+    Shaded,
+    Hidden,
+    Above,
+    Below,
+}
+
+impl fmt::Display for Property {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Property::Maximized => "maximized_vert,maximized_horz",
+            Property::Minimized => "hidden",
+            Property::Fullscreen => "fullscreen",
+            Property::Shaded => "shaded",
+            Property::Hidden => "hidden",
+            Property::Above => "above",
+            Property::Below => "below",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// A `wmctrl -b` state argument, pairing an [`Action`] with a [`Property`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct State {
+    action: Action,
+    property: Property,
+}
+
+impl State {
+    pub fn new(action: Action, property: Property) -> State {
+        State { action, property }
+    }
+
+    #[cfg(feature = "ewmh")]
+    pub(crate) fn action(&self) -> Action {
+        self.action
+    }
+
+    #[cfg(feature = "ewmh")]
+    pub(crate) fn property(&self) -> Property {
+        self.property
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.action, self.property)
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt;
+
+/// A `wmctrl -e` move/resize argument: `<GRAVITY>,<X>,<Y>,<W>,<H>`.
+///
+/// Any field can be left untouched by passing `-1`, matching `wmctrl`'s own convention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transformation {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+    gravity: i32,
+    frame_inclusive: bool,
+}
+
+impl Transformation {
+    pub fn new(x: i32, y: i32, width: i32, height: i32) -> Transformation {
+        Transformation {
+            x,
+            y,
+            width,
+            height,
+            gravity: 0,
+            frame_inclusive: false,
+        }
+    }
+
+    /// Set the gravity `wmctrl -e` should apply the move/resize with.
+    ///
+    /// Defaults to `0` (the window's own static gravity) when left unset.
+    pub fn gravity(mut self, gravity: i32) -> Transformation {
+        self.gravity = gravity;
+        self
+    }
+
+    /// Treat `x`/`y`/`width`/`height` as frame-inclusive (decoration included) geometry.
+    ///
+    /// When set, [`crate::Window::transform`] compensates using the window's
+    /// `_NET_FRAME_EXTENTS` so the window, decorations included, occupies exactly the
+    /// requested rectangle; the client area is inset from it by the frame's thickness.
+    pub fn frame_inclusive(mut self, frame_inclusive: bool) -> Transformation {
+        self.frame_inclusive = frame_inclusive;
+        self
+    }
+
+    /// Get the horizontal position set on this transformation.
+    pub fn x(&self) -> i32 {
+        self.x
+    }
+
+    /// Get the vertical position set on this transformation.
+    pub fn y(&self) -> i32 {
+        self.y
+    }
+
+    /// Get the width set on this transformation.
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    /// Get the height set on this transformation.
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// Whether this transformation treats its geometry as frame-inclusive.
+    pub fn is_frame_inclusive(&self) -> bool {
+        self.frame_inclusive
+    }
+
+    /// Get the gravity set via [`Transformation::gravity`].
+    pub fn gravity_value(&self) -> i32 {
+        self.gravity
+    }
+}
+
+impl fmt::Display for Transformation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{},{},{}", self.gravity, self.x, self.y, self.width, self.height)
+    }
+}
@@ -0,0 +1,33 @@
+use std::io::ErrorKind;
+use std::process::{Command, Output};
+
+use crate::error::WmctrlError;
+
+/// Run `program` with `args` and return its raw `Output`.
+///
+/// `program` is executed directly (no shell involved), so a missing binary surfaces as
+/// [`WmctrlError::BinaryNotFound`] instead of a shell's own "command not found" exit
+/// code, and argument values (e.g. a window title) can never be reinterpreted by a shell.
+pub(crate) fn run(program: &str, args: &[String]) -> Result<Output, WmctrlError> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| match e.kind() {
+            ErrorKind::NotFound => WmctrlError::BinaryNotFound,
+            _ => WmctrlError::Io(e),
+        })?;
+
+    if !output.status.success() {
+        return Err(WmctrlError::NonZeroExit {
+            code: output.status.code(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        });
+    }
+
+    Ok(output)
+}
+
+/// Run `wmctrl` with `args` and return its raw `Output`.
+pub(crate) fn wmctrl(args: &[String]) -> Result<Output, WmctrlError> {
+    run("wmctrl", args)
+}
@@ -1,13 +1,17 @@
+use std::cell::Cell;
+
+use crate::backend::Backend;
 use crate::desktop::get_current_desktop;
+#[cfg(feature = "ewmh")]
+use crate::ewmh;
+use crate::error::WmctrlError;
+use crate::monitor::Monitor;
 use crate::state::State;
 use crate::transformation::Transformation;
-use crate::utils::wmctrl;
+use crate::utils::{run, wmctrl};
 
 /// A type representing windows managed by the window manager.
 /// An instance is only obtainable through `wmctrl::get_windows()`
-///
-/// **Note**: Since `wmctrl` fails silently there is no warranty that the actions performed on the window will be successful.
-/// This is a flaw in the command line tool itself and not of this crate.
 #[derive(Debug)]
 pub struct Window {
     id: String,
@@ -15,7 +19,41 @@ pub struct Window {
     client_machine: String,
     title: String,
     transformation: Transformation,
-    class: String
+    class: String,
+    frame_extents: Cell<Option<(u32, u32, u32, u32)>>,
+    backend: Backend,
+}
+
+/// Shrink a frame-inclusive [`Transformation`] down to client-area geometry using the
+/// window manager's decoration thickness `(left, right, top, bottom)`.
+fn compensate_for_frame(
+    transformation: Transformation,
+    (left, right, top, bottom): (u32, u32, u32, u32),
+) -> Transformation {
+    Transformation::new(
+        transformation.x() + left as i32,
+        transformation.y() + top as i32,
+        transformation.width() - (left + right) as i32,
+        transformation.height() - (top + bottom) as i32,
+    )
+    .gravity(transformation.gravity_value())
+}
+
+/// Parse `xprop -id <WIN> _NET_FRAME_EXTENTS`'s stdout into `(left, right, top, bottom)`.
+///
+/// Defaults to `(0, 0, 0, 0)` when the property is absent (no `=` in the output) or its
+/// value doesn't parse as exactly four integers, since that just means the window manager
+/// isn't drawing decorations around this window, not that the window is gone.
+fn parse_frame_extents(stdout: &str) -> (u32, u32, u32, u32) {
+    let values: Option<Vec<u32>> = stdout
+        .split('=')
+        .nth(1)
+        .and_then(|rest| rest.split(',').map(|v| v.trim().parse().ok()).collect());
+
+    match values.as_deref() {
+        Some([left, right, top, bottom]) => (*left, *right, *top, *bottom),
+        _ => (0, 0, 0, 0),
+    }
 }
 
 impl Window {
@@ -33,40 +71,120 @@ impl Window {
             client_machine,
             title,
             transformation,
-            class
+            class,
+            frame_extents: Cell::new(None),
+            backend: Backend::Wmctrl,
         }
     }
 
-    fn get(&self) -> String {
-        format!("{} -i", self.id)
+    /// Select which [`Backend`] this window uses to carry out its actions.
+    pub fn with_backend(mut self, backend: Backend) -> Window {
+        self.backend = backend;
+        self
+    }
+
+    /// The `<WIN> -i` tokens identifying this window to `wmctrl`.
+    fn id_args(&self) -> Vec<String> {
+        vec![self.id.clone(), "-i".to_string()]
+    }
+
+    /// Build a `wmctrl -r <WIN> -i <flag> [extra...]` argument vector for this window.
+    fn targeted_args(&self, flag: &str, extra: &[&str]) -> Vec<String> {
+        let mut args = vec!["-r".to_string()];
+        args.extend(self.id_args());
+        args.push(flag.to_string());
+        args.extend(extra.iter().map(|s| s.to_string()));
+        args
+    }
+
+    /// Build a `wmctrl <flag> <WIN> -i` argument vector for this window.
+    fn flag_args(&self, flag: &str) -> Vec<String> {
+        let mut args = vec![flag.to_string()];
+        args.extend(self.id_args());
+        args
+    }
+
+    /// Check that this window is still present in the window manager's list.
+    ///
+    /// Run before every action so a stale [`Window`] fails with
+    /// [`WmctrlError::WindowNotFound`] instead of silently doing nothing.
+    fn ensure_exists(&self) -> Result<(), WmctrlError> {
+        match self.backend {
+            Backend::Wmctrl => {
+                let output = wmctrl(&["-l".to_string()])?;
+                let stdout = String::from_utf8_lossy(&output.stdout);
+
+                if stdout.lines().any(|line| {
+                    line.split_whitespace()
+                        .next()
+                        .is_some_and(|id| id == self.id)
+                }) {
+                    Ok(())
+                } else {
+                    Err(WmctrlError::WindowNotFound)
+                }
+            }
+            #[cfg(feature = "ewmh")]
+            Backend::Ewmh => {
+                if ewmh::exists(&self.id)? {
+                    Ok(())
+                } else {
+                    Err(WmctrlError::WindowNotFound)
+                }
+            }
+        }
     }
 
     /// Set the title of the window
     ///
     /// This method is the equivalent of `wmctrl -r <WIN> -N <STR>`.
-    pub fn set_title(&mut self, title: &str) {
+    pub fn set_title(&mut self, title: &str) -> Result<(), WmctrlError> {
+        self.ensure_exists()?;
         self.title = String::from(title);
 
-        let args = format!("-r {} -N {}", self.get(), title);
-        wmctrl(&args);
+        match self.backend {
+            Backend::Wmctrl => {
+                wmctrl(&self.targeted_args("-N", &[title]))?;
+            }
+            #[cfg(feature = "ewmh")]
+            Backend::Ewmh => ewmh::set_name(&self.id, title)?,
+        }
+        Ok(())
     }
 
     /// Set the icon title (short title) of the window
     ///
     /// This method is the equivalent of `wmctrl -r <WIN> -I <STR>`.
-    pub fn set_icon_title(&self, title: &str) {
-        let args = format!("-r {} -I {}", self.get(), title);
-        wmctrl(&args);
+    ///
+    /// Note: the [`Backend::Ewmh`] backend has no separate icon-title property and
+    /// falls back to setting `_NET_WM_NAME`, same as [`Window::set_title`].
+    pub fn set_icon_title(&self, title: &str) -> Result<(), WmctrlError> {
+        self.ensure_exists()?;
+        match self.backend {
+            Backend::Wmctrl => {
+                wmctrl(&self.targeted_args("-I", &[title]))?;
+            }
+            #[cfg(feature = "ewmh")]
+            Backend::Ewmh => ewmh::set_name(&self.id, title)?,
+        }
+        Ok(())
     }
 
     /// Set both the title and icon title of the window
     ///
     /// This method is the equivalent of `wmctrl -r <WIN> -T <STR>`.
-    pub fn set_both_title(&mut self, title: &str) {
+    pub fn set_both_title(&mut self, title: &str) -> Result<(), WmctrlError> {
+        self.ensure_exists()?;
         self.title = String::from(title);
 
-        let args = format!("-r {} -T {}", self.get(), title);
-        wmctrl(&args);
+        match self.backend {
+            Backend::Wmctrl => {
+                wmctrl(&self.targeted_args("-T", &[title]))?;
+            }
+            #[cfg(feature = "ewmh")]
+            Backend::Ewmh => ewmh::set_name(&self.id, title)?,
+        }
+        Ok(())
     }
 
     /// Change the state of the window
@@ -76,15 +194,22 @@ impl Window {
     ///
     /// # Examples
     ///
-    /// ```
-    /// let windows = wmctrl::get_windows();
+    /// ```no_run
+    /// let windows = wmctrl::get_windows().unwrap();
     /// let win = &windows[0];
     /// // Make the window fullscreen
-    /// win.change_state(wmctrl::State::new(wmctrl::Action::Add, wmctrl::Property::Fullscreen));
+    /// win.change_state(wmctrl::State::new(wmctrl::Action::Add, wmctrl::Property::Fullscreen)).unwrap();
     /// ```
-    pub fn change_state(&self, state: State) {
-        let args = format!("-r {} -b {}", self.get(), state);
-        wmctrl(&args);
+    pub fn change_state(&self, state: State) -> Result<(), WmctrlError> {
+        self.ensure_exists()?;
+        match self.backend {
+            Backend::Wmctrl => {
+                wmctrl(&self.targeted_args("-b", &[&state.to_string()]))?;
+            }
+            #[cfg(feature = "ewmh")]
+            Backend::Ewmh => ewmh::change_state(&self.id, state.action(), state.property())?,
+        }
+        Ok(())
     }
 
     /// Resize and move the window around the desktop
@@ -93,45 +218,148 @@ impl Window {
     ///
     /// # Examples
     ///
-    /// ```
-    /// let mut windows = wmctrl::get_windows();
+    /// ```no_run
+    /// let mut windows = wmctrl::get_windows().unwrap();
     /// let win = &mut windows[0];
     /// // This will move the window to the top left corner and resize it to 960x540
-    /// win.transform(wmctrl::Transformation::new(0, 0, 960, 540));
+    /// win.transform(wmctrl::Transformation::new(0, 0, 960, 540)).unwrap();
     /// ```
-    pub fn transform(&mut self, transformation: Transformation) {
-        self.transformation = transformation;
+    pub fn transform(&mut self, transformation: Transformation) -> Result<(), WmctrlError> {
+        self.ensure_exists()?;
+
+        self.transformation = if transformation.is_frame_inclusive() {
+            let extents = self.frame_extents()?;
+            compensate_for_frame(transformation, extents)
+        } else {
+            transformation
+        };
+
+        match self.backend {
+            Backend::Wmctrl => {
+                wmctrl(&self.targeted_args("-e", &[&self.transformation.to_string()]))?;
+            }
+            #[cfg(feature = "ewmh")]
+            Backend::Ewmh => ewmh::transform(&self.id, &self.transformation)?,
+        }
+        Ok(())
+    }
+
+    /// Read the window manager's decoration thickness for this window: `(left, right, top, bottom)`.
+    ///
+    /// This is the equivalent of `xprop -id <WIN> _NET_FRAME_EXTENTS`, cached after the first call.
+    /// Undecorated and tiling-WM windows don't set this property at all; that's reported as
+    /// `(0, 0, 0, 0)` rather than an error, since the window is still very much open.
+    pub fn frame_extents(&self) -> Result<(u32, u32, u32, u32), WmctrlError> {
+        if let Some(extents) = self.frame_extents.get() {
+            return Ok(extents);
+        }
+
+        let output = run(
+            "xprop",
+            &["-id".to_string(), self.id.clone(), "_NET_FRAME_EXTENTS".to_string()],
+        )?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        let extents = parse_frame_extents(&stdout);
+        self.frame_extents.set(Some(extents));
+        Ok(extents)
+    }
+
+    /// Resize and move the window to coordinates relative to a specific monitor
+    ///
+    /// `rel` is interpreted relative to `monitor`'s origin rather than the root window,
+    /// so the same `rel` places a window in the same spot on any monitor.
+    pub fn transform_on(&mut self, monitor: &Monitor, rel: Transformation) -> Result<(), WmctrlError> {
+        let absolute = Transformation::new(
+            monitor.x() + rel.x(),
+            monitor.y() + rel.y(),
+            rel.width(),
+            rel.height(),
+        )
+        .gravity(rel.gravity_value())
+        .frame_inclusive(rel.is_frame_inclusive());
+
+        self.transform(absolute)
+    }
 
-        let args = format!("-r {} -e {}", self.get(), &self.transformation);
-        wmctrl(&args);
+    /// Resize and move the window to fill `monitor` entirely
+    pub fn maximize_on(&mut self, monitor: &Monitor) -> Result<(), WmctrlError> {
+        self.transform_on(
+            monitor,
+            Transformation::new(0, 0, monitor.width() as i32, monitor.height() as i32),
+        )
+    }
+
+    /// Move the window to the center of `monitor`, keeping its current size
+    pub fn center_on(&mut self, monitor: &Monitor) -> Result<(), WmctrlError> {
+        let width = self.transformation.width();
+        let height = self.transformation.height();
+
+        self.transform_on(
+            monitor,
+            Transformation::new(
+                (monitor.width() as i32 - width) / 2,
+                (monitor.height() as i32 - height) / 2,
+                width,
+                height,
+            ),
+        )
     }
 
     /// Move the window to the specified desktop
     ///
     /// This method is the equivalent of `wmctrl -r <WIN> -t <DESK>`.
-    pub fn set_desktop(&mut self, desktop: &str) {
+    pub fn set_desktop(&mut self, desktop: &str) -> Result<(), WmctrlError> {
+        self.ensure_exists()?;
         self.desktop = String::from(desktop);
 
-        let args = format!("-r {} -t {}", self.get(), desktop);
-        wmctrl(&args);
+        match self.backend {
+            Backend::Wmctrl => {
+                wmctrl(&self.targeted_args("-t", &[desktop]))?;
+            }
+            #[cfg(feature = "ewmh")]
+            Backend::Ewmh => {
+                let desktop_number = desktop.parse().map_err(|_| WmctrlError::WindowNotFound)?;
+                ewmh::set_desktop(&self.id, desktop_number)?
+            }
+        }
+        Ok(())
     }
 
     /// Move the window to the current desktop and raise it
     ///
     /// This method is the equivalent of `wmctrl -R <WIN>`.
-    pub fn activate(&mut self) {
-        self.desktop = get_current_desktop();
+    pub fn activate(&mut self) -> Result<(), WmctrlError> {
+        self.ensure_exists()?;
+        self.desktop = get_current_desktop()?;
 
-        let args = format!("-R {}", self.get());
-        wmctrl(&args);
+        match self.backend {
+            Backend::Wmctrl => {
+                wmctrl(&self.flag_args("-R"))?;
+            }
+            #[cfg(feature = "ewmh")]
+            Backend::Ewmh => {
+                let desktop_number = self.desktop.parse().map_err(|_| WmctrlError::NoCurrentDesktop)?;
+                ewmh::set_desktop(&self.id, desktop_number)?;
+                ewmh::activate(&self.id)?
+            }
+        }
+        Ok(())
     }
 
     /// Activate the window by switching to its desktop and raising it
     ///
     /// This method is the equivalent of `wmctrl -a <WIN>`.
-    pub fn raise(&self) {
-        let args = format!("-a {}", self.get());
-        wmctrl(&args);
+    pub fn raise(&self) -> Result<(), WmctrlError> {
+        self.ensure_exists()?;
+        match self.backend {
+            Backend::Wmctrl => {
+                wmctrl(&self.flag_args("-a"))?;
+            }
+            #[cfg(feature = "ewmh")]
+            Backend::Ewmh => ewmh::activate(&self.id)?,
+        }
+        Ok(())
     }
 
     /// Close the window gracefully
@@ -140,16 +368,49 @@ impl Window {
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// use wmctrl::Window;
     ///
     /// // We need to move the window out of the vector so there is no reference left
-    /// let win: Window = wmctrl::get_windows().remove(0);
-    /// win.close();
+    /// let win: Window = wmctrl::get_windows().unwrap().remove(0);
+    /// win.close().unwrap();
     /// ```
-    pub fn close(self) {
-        let args = format!("-c {}", self.get());
-        wmctrl(&args);
+    pub fn close(self) -> Result<(), WmctrlError> {
+        self.ensure_exists()?;
+        match self.backend {
+            Backend::Wmctrl => {
+                wmctrl(&self.flag_args("-c"))?;
+            }
+            #[cfg(feature = "ewmh")]
+            Backend::Ewmh => ewmh::close(&self.id)?,
+        }
+        Ok(())
+    }
+
+    /// Re-synchronize every field with the window manager's current view of this window
+    ///
+    /// A [`Window`] is a snapshot taken at `get_windows()` time; if another process has
+    /// since moved, renamed, or closed it, this struct goes stale. `refresh` re-queries
+    /// the window manager and updates all fields in place, or returns
+    /// [`WmctrlError::WindowNotFound`] if the window has since closed.
+    pub fn refresh(&mut self) -> Result<(), WmctrlError> {
+        let fresh = crate::get_windows()?
+            .into_iter()
+            .find(|w| w.id == self.id)
+            .ok_or(WmctrlError::WindowNotFound)?;
+
+        self.apply_snapshot(fresh);
+        Ok(())
+    }
+
+    /// Overwrite every mutable field with `fresh`'s, keeping `id` and `backend` as-is.
+    fn apply_snapshot(&mut self, fresh: Window) {
+        self.desktop = fresh.desktop;
+        self.client_machine = fresh.client_machine;
+        self.title = fresh.title;
+        self.transformation = fresh.transformation;
+        self.class = fresh.class;
+        self.frame_extents.set(None);
     }
 
     /// Get the title immutably
@@ -164,4 +425,93 @@ impl Window {
     pub fn id(&self) -> &String {
         &self.id
     }
+
+    /// Get the number of the desktop this window was last known to be on
+    pub fn desktop(&self) -> &str {
+        &self.desktop
+    }
+
+    /// Get the hostname of the machine this window's client is running on
+    pub fn client_machine(&self) -> &str {
+        &self.client_machine
+    }
+
+    /// Get the window's last known geometry
+    pub fn transformation(&self) -> &Transformation {
+        &self.transformation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(id: &str, title: &str) -> Window {
+        Window::new(
+            id.to_string(),
+            "0".to_string(),
+            "localhost".to_string(),
+            title.to_string(),
+            Transformation::new(0, 0, 100, 100),
+            "xterm.XTerm".to_string(),
+        )
+    }
+
+    #[test]
+    fn apply_snapshot_overwrites_mutable_fields_but_keeps_id() {
+        let mut win = window("0x1", "old title");
+        win.frame_extents.set(Some((1, 1, 1, 1)));
+
+        let fresh = window("0x1", "new title");
+        win.apply_snapshot(fresh);
+
+        assert_eq!(win.id(), "0x1");
+        assert_eq!(win.title(), "new title");
+        assert_eq!(win.frame_extents.get(), None);
+    }
+
+    #[test]
+    fn getters_expose_constructed_fields() {
+        let win = window("0x2", "term");
+        assert_eq!(win.desktop(), "0");
+        assert_eq!(win.client_machine(), "localhost");
+        assert_eq!(win.transformation().width(), 100);
+    }
+
+    #[test]
+    fn parse_frame_extents_reads_four_values() {
+        let stdout = "_NET_FRAME_EXTENTS(CARDINAL) = 1, 2, 26, 3\n";
+        assert_eq!(parse_frame_extents(stdout), (1, 2, 26, 3));
+    }
+
+    #[test]
+    fn parse_frame_extents_defaults_when_property_absent() {
+        let stdout = "_NET_FRAME_EXTENTS:  not found.\n";
+        assert_eq!(parse_frame_extents(stdout), (0, 0, 0, 0));
+    }
+
+    #[test]
+    fn parse_frame_extents_defaults_on_malformed_value() {
+        let stdout = "_NET_FRAME_EXTENTS(CARDINAL) = 1, 2\n";
+        assert_eq!(parse_frame_extents(stdout), (0, 0, 0, 0));
+    }
+
+    #[test]
+    fn compensate_for_frame_shrinks_to_client_area() {
+        let requested = Transformation::new(0, 0, 1000, 800).frame_inclusive(true);
+        let compensated = compensate_for_frame(requested, (2, 3, 20, 5));
+
+        assert_eq!(compensated.x(), 2);
+        assert_eq!(compensated.y(), 20);
+        assert_eq!(compensated.width(), 1000 - 5);
+        assert_eq!(compensated.height(), 800 - 25);
+    }
+
+    #[test]
+    fn compensate_for_frame_preserves_gravity() {
+        let requested = Transformation::new(0, 0, 100, 100).gravity(5);
+        let compensated = compensate_for_frame(requested, (0, 0, 0, 0));
+        assert_eq!(compensated.gravity_value(), 5);
+    }
+
 }